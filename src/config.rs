@@ -0,0 +1,228 @@
+//! TOML configuration for calculation method, madhab, and manual angle/offset
+//! tweaks, loaded from `$XDG_CONFIG_HOME/eww_prayer_times/config.toml` (or an
+//! explicit `--config` path) and merged with CLI overrides in `main()`.
+
+use salah::prelude::*;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize, Default, Debug)]
+pub struct PrayerAdjustmentsConfig {
+    pub fajr: Option<i64>,
+    pub sunrise: Option<i64>,
+    pub dhuhr: Option<i64>,
+    pub asr: Option<i64>,
+    pub maghrib: Option<i64>,
+    pub isha: Option<i64>,
+}
+
+#[derive(Deserialize, Default, Debug)]
+pub struct Config {
+    pub method: Option<String>,
+    pub madhab: Option<String>,
+    pub high_latitude_rule: Option<String>,
+    pub fajr_angle: Option<f64>,
+    pub isha_angle: Option<f64>,
+    pub adjustments: Option<PrayerAdjustmentsConfig>,
+}
+
+impl Config {
+    /// Loads the config file at `explicit_path`, falling back to the XDG
+    /// default location when `explicit_path` is `None`. A missing file at
+    /// the XDG default is not an error; it just yields `Config::default()`.
+    /// A missing file at an explicitly-given `--config` path IS an error,
+    /// since a typo'd path silently running with defaults would go unnoticed.
+    pub fn load(explicit_path: Option<&str>) -> Result<Config, Box<dyn std::error::Error>> {
+        let path = match explicit_path {
+            Some(p) => PathBuf::from(p),
+            None => default_config_path(),
+        };
+
+        if !path.exists() {
+            if explicit_path.is_some() {
+                return Err(format!("config file '{}' not found", path.display()).into());
+            }
+            return Ok(Config::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read config file '{}': {}", path.display(), e))?;
+        let config: Config = toml::from_str(&contents)
+            .map_err(|e| format!("Failed to parse config file '{}': {}", path.display(), e))?;
+        Ok(config)
+    }
+}
+
+fn default_config_path() -> PathBuf {
+    let config_home = std::env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            Path::new(&home).join(".config")
+        });
+    config_home.join("eww_prayer_times").join("config.toml")
+}
+
+pub fn parse_method(name: &str) -> Result<Method, String> {
+    match name.to_lowercase().as_str() {
+        "muslim_world_league" | "mwl" => Ok(Method::MuslimWorldLeague),
+        "egyptian" => Ok(Method::Egyptian),
+        "karachi" => Ok(Method::Karachi),
+        "umm_al_qura" | "ummalqura" => Ok(Method::UmmAlQura),
+        "dubai" => Ok(Method::Dubai),
+        "moonsighting_committee" | "moonsighting" => Ok(Method::MoonsightingCommittee),
+        "north_america" | "isna" => Ok(Method::NorthAmerica),
+        "kuwait" => Ok(Method::Kuwait),
+        "qatar" => Ok(Method::Qatar),
+        "singapore" => Ok(Method::Singapore),
+        "other" => Ok(Method::Other),
+        other => Err(format!("Unknown calculation method '{}'.", other)),
+    }
+}
+
+pub fn parse_madhab(name: &str) -> Result<Madhab, String> {
+    match name.to_lowercase().as_str() {
+        "shafi" => Ok(Madhab::Shafi),
+        "hanafi" => Ok(Madhab::Hanafi),
+        other => Err(format!("Unknown madhab '{}'.", other)),
+    }
+}
+
+pub fn parse_high_latitude_rule(name: &str) -> Result<HighLatitudeRule, String> {
+    match name.to_lowercase().as_str() {
+        "middle_of_the_night" | "middle" => Ok(HighLatitudeRule::MiddleOfTheNight),
+        "seventh_of_the_night" | "seventh" => Ok(HighLatitudeRule::SeventhOfTheNight),
+        "twilight_angle" | "twilight" => Ok(HighLatitudeRule::TwilightAngle),
+        other => Err(format!("Unknown high-latitude rule '{}'.", other)),
+    }
+}
+
+/// Above ~48° latitude the default geometry can produce missing or
+/// nonsensical Fajr/Isha times, so pick a rule that keeps them sane instead
+/// of defaulting to `MiddleOfTheNight` everywhere.
+fn recommended_high_latitude_rule(latitude: f64) -> HighLatitudeRule {
+    if latitude.abs() > 48.0 {
+        HighLatitudeRule::SeventhOfTheNight
+    } else {
+        HighLatitudeRule::MiddleOfTheNight
+    }
+}
+
+/// Builds the `salah` `Configuration` from the config file, with any CLI
+/// flags taking priority over file values. `latitude` is the resolved
+/// location's latitude, used to auto-select a high-latitude rule when
+/// neither the config file nor the CLI specify one.
+pub fn build_configuration(
+    config: &Config,
+    cli_method: Option<&str>,
+    cli_madhab: Option<&str>,
+    cli_high_latitude_rule: Option<&str>,
+    latitude: f64,
+) -> Result<Configuration, Box<dyn std::error::Error>> {
+    let method_name = cli_method.or(config.method.as_deref());
+    let madhab_name = cli_madhab.or(config.madhab.as_deref());
+    let high_latitude_rule_name = cli_high_latitude_rule.or(config.high_latitude_rule.as_deref());
+
+    let method = match method_name {
+        Some(name) => parse_method(name)?,
+        None => Method::Singapore,
+    };
+    let madhab = match madhab_name {
+        Some(name) => parse_madhab(name)?,
+        None => Madhab::Shafi,
+    };
+    let high_latitude_rule = match high_latitude_rule_name {
+        Some(name) => parse_high_latitude_rule(name)?,
+        None => recommended_high_latitude_rule(latitude),
+    };
+
+    let mut configuration = Configuration::with(method, madhab);
+    configuration.high_latitude_rule = high_latitude_rule;
+
+    if let Some(fajr_angle) = config.fajr_angle {
+        configuration.fajr_angle = fajr_angle;
+    }
+    if let Some(isha_angle) = config.isha_angle {
+        configuration.isha_angle = isha_angle;
+    }
+    if let Some(adjustments) = &config.adjustments {
+        configuration.method_adjustments = PrayerAdjustments::new()
+            .fajr(adjustments.fajr.unwrap_or(0))
+            .sunrise(adjustments.sunrise.unwrap_or(0))
+            .dhuhr(adjustments.dhuhr.unwrap_or(0))
+            .asr(adjustments.asr.unwrap_or(0))
+            .maghrib(adjustments.maghrib.unwrap_or(0))
+            .isha(adjustments.isha.unwrap_or(0));
+    }
+
+    Ok(configuration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // `Config::load` reads `XDG_CONFIG_HOME`, a process-wide env var, so
+    // serialize the tests that touch it to avoid racing each other.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn cli_flag_overrides_config_file_value() {
+        let config = Config {
+            method: Some("singapore".to_string()),
+            madhab: Some("shafi".to_string()),
+            ..Config::default()
+        };
+        let configuration =
+            build_configuration(&config, Some("north_america"), Some("hanafi"), None, 0.0).unwrap();
+        assert!(matches!(configuration.method, Method::NorthAmerica));
+        assert!(matches!(configuration.madhab, Madhab::Hanafi));
+    }
+
+    #[test]
+    fn config_file_value_used_when_cli_absent() {
+        let config = Config {
+            method: Some("umm_al_qura".to_string()),
+            madhab: Some("hanafi".to_string()),
+            ..Config::default()
+        };
+        let configuration = build_configuration(&config, None, None, None, 0.0).unwrap();
+        assert!(matches!(configuration.method, Method::UmmAlQura));
+        assert!(matches!(configuration.madhab, Madhab::Hanafi));
+    }
+
+    #[test]
+    fn recommended_high_latitude_rule_switches_at_48_degrees() {
+        assert!(matches!(
+            recommended_high_latitude_rule(48.0),
+            HighLatitudeRule::MiddleOfTheNight
+        ));
+        assert!(matches!(
+            recommended_high_latitude_rule(48.01),
+            HighLatitudeRule::SeventhOfTheNight
+        ));
+        assert!(matches!(
+            recommended_high_latitude_rule(-59.0),
+            HighLatitudeRule::SeventhOfTheNight
+        ));
+    }
+
+    #[test]
+    fn load_errors_only_when_explicit_config_path_is_missing() {
+        let _guard = ENV_LOCK.lock().unwrap();
+
+        let explicit_result = Config::load(Some("/nonexistent/path/config.toml"));
+        assert!(explicit_result.is_err());
+
+        let previous_xdg = std::env::var("XDG_CONFIG_HOME").ok();
+        std::env::set_var("XDG_CONFIG_HOME", "/nonexistent/xdg/config/home");
+        let default_result = Config::load(None);
+        match previous_xdg {
+            Some(value) => std::env::set_var("XDG_CONFIG_HOME", value),
+            None => std::env::remove_var("XDG_CONFIG_HOME"),
+        }
+
+        assert!(default_result.is_ok());
+    }
+}
@@ -1,18 +1,26 @@
-use chrono::{DateTime, Datelike, Local, NaiveTime, TimeZone};
+mod config;
+mod printer;
+mod timeparse;
+mod zone;
+
+use chrono::{DateTime, FixedOffset};
 use clap::Parser;
 use notify_rust::Notification;
+use printer::OutputFormat;
 use rust_embed::RustEmbed;
 use salah::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::io::{self, Write};
 use std::sync::OnceLock;
 use std::thread;
 use std::time::Duration;
+use zone::Zone;
 
 #[derive(Serialize)]
 struct PrayerOutput {
     #[serde(rename = "Fajr")]
     fajr: String,
+    #[serde(rename = "Sunrise")]
+    sunrise: String,
     #[serde(rename = "Dhuhr")]
     dhuhr: String,
     #[serde(rename = "Asr")]
@@ -22,6 +30,7 @@ struct PrayerOutput {
     #[serde(rename = "Isha")]
     isha: String,
     next: String,
+    qibla: f64,
 }
 
 #[derive(Parser)]
@@ -33,6 +42,38 @@ struct Cli {
     #[clap(long)]
     coordinate: Option<String>,
 
+    /// Path to a config.toml, overriding the XDG default location.
+    #[clap(long)]
+    config: Option<String>,
+
+    /// Calculation method, e.g. `north_america`, `umm_al_qura`, `singapore`. Overrides the config file.
+    #[clap(long)]
+    method: Option<String>,
+
+    /// Madhab for Asr calculation: `shafi` or `hanafi`. Overrides the config file.
+    #[clap(long)]
+    madhab: Option<String>,
+
+    /// High-latitude rule: `middle`, `seventh`, or `twilight`. Overrides the config file; defaults to an auto choice based on latitude.
+    #[clap(long)]
+    high_latitude_rule: Option<String>,
+
+    /// Output format: `json` (default, for eww), `plain`, or `color`.
+    #[clap(long, value_enum, default_value_t = OutputFormat::Json)]
+    format: OutputFormat,
+
+    /// Compute, print, and exit instead of running as a daemon.
+    #[clap(long)]
+    once: bool,
+
+    /// IANA timezone (e.g. `Asia/Jakarta`) to compute and display times in. Defaults to the system's local zone.
+    #[clap(long)]
+    timezone: Option<String>,
+
+    /// Print the qibla bearing (degrees from true north) for the resolved location and exit.
+    #[clap(long)]
+    qibla: bool,
+
     #[clap(long, hide = true)]
     test_at: Option<String>,
 }
@@ -112,44 +153,56 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Err("Please provide either --city or --coordinate".into());
     };
 
+    let qibla_bearing = Qibla::from(coords).0;
+
+    if cli.qibla {
+        println!("{:.1}", qibla_bearing);
+        return Ok(());
+    }
+
+    let file_config = config::Config::load(cli.config.as_deref())?;
+    let configuration = config::build_configuration(
+        &file_config,
+        cli.method.as_deref(),
+        cli.madhab.as_deref(),
+        cli.high_latitude_rule.as_deref(),
+        coords.latitude,
+    )?;
+
+    let zone = match &cli.timezone {
+        Some(name) => Zone::parse(name)?,
+        None => Zone::Local,
+    };
+
     // Determine if we are in test mode and get the fake "now".
-    let test_now: Option<DateTime<Local>> = if let Some(test_at_str) = &cli.test_at {
-        // The format is now just "HH:MM"
-        let time = NaiveTime::parse_from_str(test_at_str, "%H:%M")?;
-        let today = Local::now().date_naive();
-        let naive_dt = today.and_time(time);
-        Some(
-            Local
-                .from_local_datetime(&naive_dt)
-                .single()
-                .ok_or("Ambiguous or invalid time provided for --test-at")?,
-        )
-    } else {
-        None
+    let test_now: Option<DateTime<FixedOffset>> = match &cli.test_at {
+        Some(test_at_str) => Some(timeparse::parse(test_at_str, zone.now(), &zone)?),
+        None => None,
     };
 
     // Main loop to run continuously as a daemon, or once if in test mode.
     loop {
         // --- Calculate Prayer Times ---
         // Use the fake time if in test mode, otherwise use the real current time.
-        let now = test_now.unwrap_or_else(Local::now);
+        let now = test_now.unwrap_or_else(|| zone.now());
         let local_date = now.date_naive();
-        let configuration = Configuration::with(Method::Singapore, Madhab::Shafi);
         let prayers = PrayerSchedule::new()
             .on(local_date)
             .for_location(coords)
-            .with_configuration(configuration)
+            .with_configuration(configuration.clone())
             .calculate()?;
 
-        let fajr_time = prayers.time(Prayer::Fajr).with_timezone(&Local);
-        let dhuhr_time = prayers.time(Prayer::Dhuhr).with_timezone(&Local);
-        let asr_time = prayers.time(Prayer::Asr).with_timezone(&Local);
-        let maghrib_time = prayers.time(Prayer::Maghrib).with_timezone(&Local);
-        let isha_time = prayers.time(Prayer::Isha).with_timezone(&Local);
+        let fajr_time = zone.from_utc(prayers.time(Prayer::Fajr));
+        let sunrise_time = zone.from_utc(prayers.time(Prayer::Sunrise));
+        let dhuhr_time = zone.from_utc(prayers.time(Prayer::Dhuhr));
+        let asr_time = zone.from_utc(prayers.time(Prayer::Asr));
+        let maghrib_time = zone.from_utc(prayers.time(Prayer::Maghrib));
+        let isha_time = zone.from_utc(prayers.time(Prayer::Isha));
 
         // --- Determine Next Prayer ---
         let prayer_times = [
             (Prayer::Fajr, fajr_time),
+            (Prayer::Sunrise, sunrise_time),
             (Prayer::Dhuhr, dhuhr_time),
             (Prayer::Asr, asr_time),
             (Prayer::Maghrib, maghrib_time),
@@ -165,18 +218,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         let output_struct = PrayerOutput {
             fajr: fajr_time.format("%H:%M").to_string(),
+            sunrise: sunrise_time.format("%H:%M").to_string(),
             dhuhr: dhuhr_time.format("%H:%M").to_string(),
             asr: asr_time.format("%H:%M").to_string(),
             maghrib: maghrib_time.format("%H:%M").to_string(),
             isha: isha_time.format("%H:%M").to_string(),
             next: next_prayer_name_for_json,
+            qibla: qibla_bearing,
         };
 
-        {
-            let stdout = io::stdout();
-            let mut handle = stdout.lock();
-            serde_json::to_writer(&mut handle, &output_struct)?;
-            writeln!(&mut handle)?;
+        printer::print(cli.format, &output_struct)?;
+
+        // --- Once Mode: Print and Exit ---
+        if cli.once {
+            break;
         }
 
         // --- Sleep Until Next Prayer and Notify ---
@@ -192,17 +247,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             thread::sleep(sleep_duration);
 
-            let prayer_name_str = format!("{:?}", prayer);
-            let summary = format!("Waktu Sholat {}", prayer_name_str);
-            let body = format!("Saatnya menunaikan sholat {}", prayer_name_str);
-            Notification::new().summary(&summary).body(&body).show()?;
+            // Sunrise isn't a prayer, so don't call it one in the notification.
+            if matches!(prayer, Prayer::Sunrise) {
+                Notification::new()
+                    .summary("Waktu Syuruq")
+                    .body("Matahari telah terbit, waktu Fajr telah berakhir.")
+                    .show()?;
+            } else {
+                let prayer_name_str = format!("{:?}", prayer);
+                let summary = format!("Waktu Sholat {}", prayer_name_str);
+                let body = format!("Saatnya menunaikan sholat {}", prayer_name_str);
+                Notification::new().summary(&summary).body(&body).show()?;
+            }
 
             thread::sleep(Duration::from_secs(1));
         } else {
-            let tomorrow = local_date.succ_opt().unwrap();
-            let midnight_local = Local
-                .with_ymd_and_hms(tomorrow.year(), tomorrow.month(), tomorrow.day(), 0, 0, 1)
-                .unwrap();
+            let tomorrow = local_date.succ_opt().ok_or("Date overflow computing next midnight")?;
+            let midnight_local = zone.resolve_local(
+                tomorrow
+                    .and_hms_opt(0, 0, 1)
+                    .ok_or("Invalid time computing next midnight")?,
+            )?;
             let sleep_duration = (midnight_local - now).to_std()?;
 
             if test_now.is_some() {
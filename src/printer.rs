@@ -0,0 +1,70 @@
+//! Renders prayer times to stdout in the format the user asked for via
+//! `--format`: `json` keeps the original newline-delimited output eww
+//! consumes, `plain` prints human-readable `Fajr: 05:12` lines, and `color`
+//! does the same with the next prayer highlighted using ANSI escapes.
+
+use crate::PrayerOutput;
+use clap::ValueEnum;
+use std::fmt;
+use std::io::{self, Write};
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Plain,
+    Color,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Plain => write!(f, "plain"),
+            OutputFormat::Color => write!(f, "color"),
+        }
+    }
+}
+
+const HIGHLIGHT: &str = "\x1b[1;32m";
+const RESET: &str = "\x1b[0m";
+
+pub fn print(format: OutputFormat, output: &PrayerOutput) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        OutputFormat::Json => print_json(output),
+        OutputFormat::Plain => print_lines(output, false),
+        OutputFormat::Color => print_lines(output, true),
+    }
+}
+
+fn print_json(output: &PrayerOutput) -> Result<(), Box<dyn std::error::Error>> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    serde_json::to_writer(&mut handle, output)?;
+    writeln!(&mut handle)?;
+    Ok(())
+}
+
+fn print_lines(output: &PrayerOutput, colorize: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let rows = [
+        ("Fajr", &output.fajr),
+        ("Sunrise", &output.sunrise),
+        ("Dhuhr", &output.dhuhr),
+        ("Asr", &output.asr),
+        ("Maghrib", &output.maghrib),
+        ("Isha", &output.isha),
+    ];
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    for (name, time) in rows {
+        if colorize && name == output.next {
+            writeln!(&mut handle, "{HIGHLIGHT}{name}: {time}{RESET}")?;
+        } else {
+            writeln!(&mut handle, "{name}: {time}")?;
+        }
+    }
+    writeln!(&mut handle, "Qibla: {:.1}\u{b0}", output.qibla)?;
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//! Flexible parsing for `--test-at`, accepting `HH:MM` on today's date, a
+//! full `YYYY-MM-DD HH:MM`, an ISO-8601 datetime, or a relative offset like
+//! `+90m` or `tomorrow 05:00`. This lets developers exercise
+//! Fajr-before-midnight and next-day rollover edge cases without touching
+//! the code.
+
+use crate::zone::Zone;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, NaiveTime};
+use regex::Regex;
+use std::sync::OnceLock;
+
+fn relative_offset_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\+(\d+)([smhd])$").expect("static regex is valid"))
+}
+
+fn tomorrow_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)^tomorrow\s+(\d{1,2}):(\d{2})$").expect("static regex is valid"))
+}
+
+/// Resolves a `--test-at` string against `now` and the given `zone`,
+/// producing the `DateTime<FixedOffset>` the daemon loop should treat as the
+/// current time.
+pub fn parse(
+    input: &str,
+    now: DateTime<FixedOffset>,
+    zone: &Zone,
+) -> Result<DateTime<FixedOffset>, Box<dyn std::error::Error>> {
+    let input = input.trim();
+
+    if let Some(caps) = relative_offset_re().captures(input) {
+        let amount: i64 = caps[1].parse()?;
+        let duration = match &caps[2] {
+            "s" => chrono::Duration::seconds(amount),
+            "m" => chrono::Duration::minutes(amount),
+            "h" => chrono::Duration::hours(amount),
+            "d" => chrono::Duration::days(amount),
+            _ => unreachable!("regex only captures s, m, h, or d"),
+        };
+        return Ok(now + duration);
+    }
+
+    if let Some(caps) = tomorrow_re().captures(input) {
+        let hour: u32 = caps[1].parse()?;
+        let minute: u32 = caps[2].parse()?;
+        let time =
+            NaiveTime::from_hms_opt(hour, minute, 0).ok_or("Invalid time in 'tomorrow HH:MM'")?;
+        let tomorrow = now
+            .date_naive()
+            .succ_opt()
+            .ok_or("Date overflow computing 'tomorrow'")?;
+        return Ok(zone.resolve_local(tomorrow.and_time(time))?);
+    }
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Ok(dt);
+    }
+
+    if let Ok(naive) = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") {
+        return Ok(zone.resolve_local(naive)?);
+    }
+
+    if let Ok(time) = NaiveTime::parse_from_str(input, "%H:%M") {
+        let today = now.date_naive();
+        return Ok(zone.resolve_local(today.and_time(time))?);
+    }
+
+    Err(format!("Could not parse '--test-at' value '{}'", input).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zone::Zone;
+    use chrono::TimeZone;
+
+    fn utc_at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<FixedOffset> {
+        chrono_tz::UTC
+            .with_ymd_and_hms(y, mo, d, h, mi, 0)
+            .unwrap()
+            .fixed_offset()
+    }
+
+    #[test]
+    fn parses_relative_minute_offset() {
+        let now = utc_at(2024, 1, 15, 10, 0);
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(parse("+90m", now, &zone).unwrap(), utc_at(2024, 1, 15, 11, 30));
+    }
+
+    #[test]
+    fn parses_relative_day_offset() {
+        let now = utc_at(2024, 1, 15, 10, 0);
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(parse("+2d", now, &zone).unwrap(), utc_at(2024, 1, 17, 10, 0));
+    }
+
+    #[test]
+    fn parses_tomorrow_with_time() {
+        let now = utc_at(2024, 1, 15, 23, 0);
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(
+            parse("tomorrow 05:00", now, &zone).unwrap(),
+            utc_at(2024, 1, 16, 5, 0)
+        );
+    }
+
+    #[test]
+    fn parses_rfc3339() {
+        let now = utc_at(2024, 1, 15, 10, 0);
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(
+            parse("2024-01-16T03:30:00+00:00", now, &zone).unwrap(),
+            utc_at(2024, 1, 16, 3, 30)
+        );
+    }
+
+    #[test]
+    fn parses_full_date_and_time() {
+        let now = utc_at(2024, 1, 15, 10, 0);
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(
+            parse("2024-01-16 04:45", now, &zone).unwrap(),
+            utc_at(2024, 1, 16, 4, 45)
+        );
+    }
+
+    #[test]
+    fn parses_bare_time_on_todays_date() {
+        let now = utc_at(2024, 1, 15, 10, 0);
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert_eq!(parse("05:12", now, &zone).unwrap(), utc_at(2024, 1, 15, 5, 12));
+    }
+
+    #[test]
+    fn rejects_unparsable_input() {
+        let now = utc_at(2024, 1, 15, 10, 0);
+        let zone = Zone::Named(chrono_tz::UTC);
+        assert!(parse("not a time", now, &zone).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! Resolves the timezone prayer times are computed and displayed in.
+//! Defaults to the system's `Local` zone, but can be pinned to an IANA zone
+//! via `--timezone` so a city in another timezone isn't silently computed
+//! against the machine's own clock, and DST ambiguity is handled explicitly
+//! instead of panicking.
+
+use chrono::{DateTime, FixedOffset, Local, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+#[derive(Clone, Copy)]
+pub enum Zone {
+    Local,
+    Named(Tz),
+}
+
+impl Zone {
+    pub fn parse(name: &str) -> Result<Zone, String> {
+        name.parse::<Tz>()
+            .map(Zone::Named)
+            .map_err(|_| format!("Unknown IANA timezone '{}'.", name))
+    }
+
+    pub fn now(&self) -> DateTime<FixedOffset> {
+        match self {
+            Zone::Local => Local::now().fixed_offset(),
+            Zone::Named(tz) => Utc::now().with_timezone(tz).fixed_offset(),
+        }
+    }
+
+    pub fn from_utc(&self, utc: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Zone::Local => utc.with_timezone(&Local).fixed_offset(),
+            Zone::Named(tz) => utc.with_timezone(tz).fixed_offset(),
+        }
+    }
+
+    /// Resolves a naive datetime as wall-clock time in this zone, preferring
+    /// the earlier instant when ambiguous (e.g. a fall-back DST transition)
+    /// and erroring rather than panicking when it falls in a spring-forward
+    /// gap that doesn't exist.
+    pub fn resolve_local(&self, naive: NaiveDateTime) -> Result<DateTime<FixedOffset>, String> {
+        let result = match self {
+            Zone::Local => Local.from_local_datetime(&naive).map(|dt| dt.fixed_offset()),
+            Zone::Named(tz) => tz.from_local_datetime(&naive).map(|dt| dt.fixed_offset()),
+        };
+        match result {
+            LocalResult::Single(dt) => Ok(dt),
+            LocalResult::Ambiguous(earlier, _later) => Ok(earlier),
+            LocalResult::None => Err(format!(
+                "'{}' falls in a DST gap with no valid local time in this timezone",
+                naive
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn resolves_unambiguous_local_time_in_named_zone() {
+        let zone = Zone::Named(chrono_tz::America::New_York);
+        let naive = NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let resolved = zone.resolve_local(naive).unwrap();
+        assert_eq!(resolved.offset().local_minus_utc(), -5 * 3600);
+    }
+
+    #[test]
+    fn resolves_ambiguous_fall_back_to_the_earlier_instant() {
+        let zone = Zone::Named(chrono_tz::America::New_York);
+        // Clocks in America/New_York fall back from 02:00 EDT to 01:00 EST on
+        // 2023-11-05, so 01:30 local occurs twice.
+        let naive = NaiveDate::from_ymd_opt(2023, 11, 5)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap();
+        let resolved = zone.resolve_local(naive).unwrap();
+        assert_eq!(resolved.offset().local_minus_utc(), -4 * 3600);
+    }
+
+    #[test]
+    fn errors_on_spring_forward_gap() {
+        let zone = Zone::Named(chrono_tz::America::New_York);
+        // Clocks in America/New_York spring forward from 02:00 to 03:00 on
+        // 2023-03-12, so 02:30 local never occurs.
+        let naive = NaiveDate::from_ymd_opt(2023, 3, 12)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        assert!(zone.resolve_local(naive).is_err());
+    }
+}